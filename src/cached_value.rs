@@ -0,0 +1,157 @@
+/// A lazily initialized value that caches itself after its first initialization.
+///
+/// Unlike [`CachedFn`](crate::CachedFn), a [`CachedValue`] does not store an initializer closure
+/// up front: the closure is supplied at the call site, via [`get_or_init`](Self::get_or_init) or
+/// [`get_or_try_init`](Self::get_or_try_init). This is a better fit when the same cell can be
+/// initialized by different code paths, or when `Output` is small but the initializing closure
+/// would be an awkwardly large `F` to store alongside it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CachedValue<Output>(CachedValueInner<Output>);
+
+/// Internal state of a [`CachedValue`].
+///
+/// This enum tracks whether the value has been computed, is still pending, or was poisoned due to
+/// a failed or in-progress initialization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CachedValueInner<Output> {
+    /// No value has been computed yet.
+    NotCalled,
+
+    /// A value has been computed and cached.
+    Called(Output),
+
+    /// Internal poisoned state, set during initialization and left in place if the initializer
+    /// panics or returns an error.
+    ///
+    /// This prevents reentrancy and ensures partial computation doesn't leak.
+    Poisoned,
+}
+
+impl<Output> CachedValueInner<Output> {
+    #[must_use]
+    #[inline]
+    const fn as_called_mut(&mut self) -> Option<&mut Output> {
+        match self {
+            Self::Called(output) => Some(output),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    const fn set_called(&mut self, output: Output) -> Self {
+        ::core::mem::replace(self, Self::Called(output))
+    }
+
+    #[inline]
+    const fn set_poisoned(&mut self) -> Self {
+        ::core::mem::replace(self, Self::Poisoned)
+    }
+}
+
+impl<Output> Default for CachedValue<Output> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Output> CachedValue<Output> {
+    /// Creates a new, uninitialized [`CachedValue`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self(CachedValueInner::NotCalled)
+    }
+
+    /// Returns the cached value if it has already been initialized.
+    #[must_use]
+    #[inline]
+    pub const fn get(&self) -> Option<&Output> {
+        match &self.0 {
+            CachedValueInner::Called(output) => Some(output),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the cached value if it has already been initialized.
+    #[must_use]
+    #[inline]
+    pub const fn get_mut(&mut self) -> Option<&mut Output> {
+        match &mut self.0 {
+            CachedValueInner::Called(output) => Some(output),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the value has been initialized and cached.
+    #[must_use]
+    #[inline]
+    pub const fn is_called(&self) -> bool {
+        matches!(self.0, CachedValueInner::Called(_))
+    }
+
+    /// Returns `true` if the value has not yet been initialized.
+    #[must_use]
+    #[inline]
+    pub const fn is_not_called(&self) -> bool {
+        matches!(self.0, CachedValueInner::NotCalled)
+    }
+
+    /// Returns `true` if this [`CachedValue`] is in a poisoned state.
+    #[must_use]
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        matches!(self.0, CachedValueInner::Poisoned)
+    }
+
+    /// Returns the cached value, initializing it with `f` if it hasn't been computed yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`CachedValue`] is in a *poisoned* state.
+    /// This can occur if a previous initializer panicked during execution.
+    ///
+    /// Once poisoned, the instance is considered unusable and further calls will panic.
+    pub fn get_or_init(&mut self, f: impl FnOnce() -> Output) -> &mut Output {
+        let inner = &mut self.0;
+        match inner {
+            CachedValueInner::NotCalled => {
+                inner.set_poisoned();
+                inner.set_called(f());
+                inner.as_called_mut().unwrap()
+            }
+            CachedValueInner::Called(output) => output,
+            CachedValueInner::Poisoned => panic!("poisoned"),
+        }
+    }
+
+    /// Returns the cached value, initializing it with the fallible `f` if it hasn't been computed
+    /// yet.
+    ///
+    /// If `f` returns an error, the [`CachedValue`] enters a **poisoned** state. Once poisoned,
+    /// the instance must be **dropped** and never reused.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`CachedValue`] is in a *poisoned* state.
+    /// This can occur if:
+    /// - A previous initializer panicked during execution, or
+    /// - A prior fallible call to [`get_or_try_init`](Self::get_or_try_init) returned an error.
+    ///
+    /// Once poisoned, the instance is considered unusable and further calls will panic.
+    pub fn get_or_try_init<E>(
+        &mut self,
+        f: impl FnOnce() -> Result<Output, E>,
+    ) -> Result<&mut Output, E> {
+        let inner = &mut self.0;
+        match inner {
+            CachedValueInner::NotCalled => {
+                inner.set_poisoned();
+                inner.set_called(f()?);
+                Ok(inner.as_called_mut().unwrap())
+            }
+            CachedValueInner::Called(output) => Ok(output),
+            CachedValueInner::Poisoned => panic!("poisoned"),
+        }
+    }
+}
@@ -2,6 +2,15 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod cached_value;
+mod once_cached_fn;
+
+pub use cached_value::CachedValue;
+pub use once_cached_fn::OnceCachedFn;
+
 /// A lazily evaluated function that caches its result after the first call.
 ///
 /// Once the function is called, its output is stored and subsequent calls will return the cached
@@ -11,8 +20,8 @@ pub struct CachedFn<F, Output>(CachedFnInner<F, Output>);
 
 /// Internal state of a [`CachedFn`].
 ///
-/// This enum tracks whether the function has been called, is still pending, or was poisoned due to
-/// a failed or in-progress call.
+/// This enum tracks whether the function has been called, is still pending, is currently
+/// running, or was poisoned due to a panic or an error.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum CachedFnInner<F, Output> {
     /// Function has not been called yet.
@@ -21,22 +30,18 @@ enum CachedFnInner<F, Output> {
     /// Function has been called and its result cached.
     Called(Output),
 
-    /// Internal poisoned state, set temporarily during a call.
+    /// Set temporarily while the function is executing.
     ///
-    /// This prevents reentrancy and ensures partial computation doesn't leak.
-    Poisoned,
+    /// Every entry point takes `&mut self`, so this state can never be observed from outside the
+    /// call that set it: reaching it again would require a second `&mut CachedFn` to the same
+    /// instance, which the borrow checker rules out.
+    Running,
+
+    /// Poisoned due to a panic or an error. See [`PoisonReason`].
+    Poisoned(PoisonReason),
 }
 
 impl<F, Output> CachedFnInner<F, Output> {
-    #[must_use]
-    #[inline]
-    fn into_not_called(self) -> Option<F> {
-        match self {
-            Self::NotCalled(f) => Some(f),
-            _ => None,
-        }
-    }
-
     #[must_use]
     #[inline]
     const fn as_called_mut(&mut self) -> Option<&mut Output> {
@@ -57,11 +62,76 @@ impl<F, Output> CachedFnInner<F, Output> {
     }
 
     #[inline]
-    const fn set_poisoned(&mut self) -> Self {
-        ::core::mem::replace(self, Self::Poisoned)
+    const fn set_poisoned(&mut self, reason: PoisonReason) -> Self {
+        ::core::mem::replace(self, Self::Poisoned(reason))
+    }
+
+    /// Transitions out of [`NotCalled`](Self::NotCalled) into [`Running`](Self::Running),
+    /// returning the function that was stored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` isn't [`NotCalled`](Self::NotCalled).
+    #[must_use]
+    #[inline]
+    fn begin_running(&mut self) -> F {
+        match ::core::mem::replace(self, Self::Running) {
+            Self::NotCalled(f) => f,
+            _ => unreachable!("begin_running called on a CachedFnInner that is not NotCalled"),
+        }
+    }
+}
+
+/// Runs `f` while `inner` is expected to be [`CachedFnInner::Running`], poisoning it with
+/// [`PoisonReason::Panicked`] if `f` unwinds.
+fn run_guarded<F, Output, R>(inner: &mut CachedFnInner<F, Output>, f: impl FnOnce() -> R) -> R {
+    struct PoisonOnUnwind<'a, F, Output> {
+        inner: &'a mut CachedFnInner<F, Output>,
+        finished: bool,
+    }
+
+    impl<F, Output> Drop for PoisonOnUnwind<'_, F, Output> {
+        fn drop(&mut self) {
+            if !self.finished {
+                *self.inner = CachedFnInner::Poisoned(PoisonReason::Panicked);
+            }
+        }
+    }
+
+    let mut guard = PoisonOnUnwind { inner, finished: false };
+    let output = f();
+    guard.finished = true;
+    output
+}
+
+/// The reason a [`CachedFn`] is in a poisoned state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoisonReason {
+    /// The wrapped function panicked while executing.
+    Panicked,
+
+    /// A fallible call returned an error.
+    Errored,
+}
+
+impl PoisonReason {
+    #[must_use]
+    #[inline]
+    const fn panic_message(self) -> &'static str {
+        match self {
+            Self::Panicked => "CachedFn is poisoned: the wrapped function previously panicked",
+            Self::Errored => "CachedFn is poisoned: a previous fallible call returned an error",
+        }
     }
 }
 
+/// Message for the `unreachable!` hit if [`CachedFnInner::Running`] is ever observed from an
+/// entry point. Every entry point takes `&mut self`, so this is only reachable through unsound
+/// `unsafe` code that aliases a `CachedFn` already being called.
+const RUNNING_UNREACHABLE_MSG: &str =
+    "CachedFn entry point observed CachedFnInner::Running; this requires unsound aliasing of \
+     &mut CachedFn and should be impossible in safe code";
+
 impl<F, Output> CachedFn<F, Output> {
     /// Creates a new [`CachedFn`] wrapping the given function.
     #[must_use]
@@ -146,7 +216,139 @@ impl<F, Output> CachedFn<F, Output> {
     #[must_use]
     #[inline]
     pub const fn is_poisoned(&self) -> bool {
-        matches!(self.0, CachedFnInner::Poisoned)
+        matches!(self.0, CachedFnInner::Poisoned(_))
+    }
+
+    /// Returns the reason this [`CachedFn`] is poisoned, or `None` if it isn't.
+    #[must_use]
+    #[inline]
+    pub const fn poison_reason(&self) -> Option<PoisonReason> {
+        match self.0 {
+            CachedFnInner::Poisoned(reason) => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Runs the given closure and caches its result if the [`CachedFn`] hasn’t been called yet or
+    /// is poisoned; returns the cached output otherwise.
+    ///
+    /// Unlike [`call`](Self::call), this never panics because of poisoning. If the instance is
+    /// poisoned, `f` is run with a [`PoisonState`] reporting `true`, and its result is cached and
+    /// returned as if the [`CachedFn`] had never been poisoned. This lets a caller deliberately
+    /// re-initialize after a failed computation instead of having to drop the instance.
+    ///
+    /// If the instance hasn’t been called yet, `f` is run with a [`PoisonState`] reporting
+    /// `false`, regardless of the function the [`CachedFn`] was constructed with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called again from within `f` itself (e.g. if `f` somehow obtains another
+    /// `&mut` reference to the same [`CachedFn`] and calls back into it).
+    pub fn call_force(&mut self, f: impl FnOnce(&PoisonState) -> Output) -> &mut Output {
+        let inner = &mut self.0;
+        match inner {
+            CachedFnInner::NotCalled(_) => {
+                let _ = inner.begin_running();
+                let output = run_guarded(&mut *inner, || f(&PoisonState { poisoned: false }));
+                inner.set_called(output);
+                inner.as_called_mut().unwrap()
+            }
+            CachedFnInner::Called(_) => inner.as_called_mut().unwrap(),
+            CachedFnInner::Running => unreachable!("{RUNNING_UNREACHABLE_MSG}"),
+            CachedFnInner::Poisoned(_) => {
+                *inner = CachedFnInner::Running;
+                let output = run_guarded(&mut *inner, || f(&PoisonState { poisoned: true }));
+                inner.set_called(output);
+                inner.as_called_mut().unwrap()
+            }
+        }
+    }
+
+    /// Fallible variant of [`call_force`](Self::call_force).
+    ///
+    /// If `f` returns an error, the [`CachedFn`] is left (or stays) poisoned, so that a later
+    /// [`call_force`](Self::call_force) or [`try_call_force`](Self::try_call_force) can attempt
+    /// recovery again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called again from within `f` itself (e.g. if `f` somehow obtains another
+    /// `&mut` reference to the same [`CachedFn`] and calls back into it).
+    pub fn try_call_force<E>(
+        &mut self,
+        f: impl FnOnce(&PoisonState) -> Result<Output, E>,
+    ) -> Result<&mut Output, E> {
+        let inner = &mut self.0;
+        match inner {
+            CachedFnInner::NotCalled(_) => {
+                let _ = inner.begin_running();
+                match run_guarded(&mut *inner, || f(&PoisonState { poisoned: false })) {
+                    Ok(output) => {
+                        inner.set_called(output);
+                        Ok(inner.as_called_mut().unwrap())
+                    }
+                    Err(err) => {
+                        inner.set_poisoned(PoisonReason::Errored);
+                        Err(err)
+                    }
+                }
+            }
+            CachedFnInner::Called(_) => Ok(inner.as_called_mut().unwrap()),
+            CachedFnInner::Running => unreachable!("{RUNNING_UNREACHABLE_MSG}"),
+            CachedFnInner::Poisoned(_) => {
+                *inner = CachedFnInner::Running;
+                match run_guarded(&mut *inner, || f(&PoisonState { poisoned: true })) {
+                    Ok(output) => {
+                        inner.set_called(output);
+                        Ok(inner.as_called_mut().unwrap())
+                    }
+                    Err(err) => {
+                        inner.set_poisoned(PoisonReason::Errored);
+                        Err(err)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The poisoning state of a [`CachedFn`] at the time [`call_force`](CachedFn::call_force) or
+/// [`try_call_force`](CachedFn::try_call_force) invoked its recovery closure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoisonState {
+    poisoned: bool,
+}
+
+impl PoisonState {
+    /// Returns `true` if the [`CachedFn`] was poisoned before this call.
+    #[must_use]
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+}
+
+/// An error returned by the `checked_*` family of methods when a [`CachedFn`] is poisoned.
+///
+/// Unlike the plain `call`/`try_call` methods, the `checked_*` methods never panic on poison:
+/// they return this error instead, carrying a guard `T` back to the caller so the state can be
+/// inspected or the instance recovered via [`into_inner`](Self::into_inner).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoisonError<T> {
+    guard: T,
+}
+
+impl<T> PoisonError<T> {
+    #[inline]
+    const fn new(guard: T) -> Self {
+        Self { guard }
+    }
+
+    /// Consumes this error, returning the guard that was poisoned.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.guard
     }
 }
 
@@ -160,36 +362,51 @@ where
     ///
     /// # Panics
     ///
-    /// Panics if the [`CachedFn`] is in a *poisoned* state.  
-    /// This can occur if the wrapped function `f` previously panicked during execution.
+    /// Panics if the [`CachedFn`] is in a *poisoned* state.
     ///
-    /// Once poisoned, the instance is considered unusable and further calls will panic.
+    /// Once poisoned, further calls keep panicking until the instance is recovered via
+    /// [`call_force`](Self::call_force). Use [`checked_call`](Self::checked_call) to get a
+    /// [`PoisonError`] instead of a panic, and [`poison_reason`](Self::poison_reason) to tell the
+    /// cause apart.
     pub fn call(&mut self) -> &mut Output {
         let inner = &mut self.0;
         match inner {
             CachedFnInner::NotCalled(_) => {
-                let f = inner.set_poisoned().into_not_called().unwrap();
-                inner.set_called(f());
+                let f = inner.begin_running();
+                let output = run_guarded(&mut *inner, f);
+                inner.set_called(output);
                 inner.as_called_mut().unwrap()
             }
             CachedFnInner::Called(res) => res,
-            CachedFnInner::Poisoned => panic!("poisoned"),
+            CachedFnInner::Running => unreachable!("{RUNNING_UNREACHABLE_MSG}"),
+            CachedFnInner::Poisoned(reason) => panic!("{}", reason.panic_message()),
+        }
+    }
+
+    /// Calls the function if it hasn’t been called yet and caches its result, without panicking
+    /// if the [`CachedFn`] is poisoned.
+    ///
+    /// Returns `Err(`[`PoisonError`]`)` if the instance is poisoned instead of panicking. The
+    /// error carries `&mut self` back, so the caller can inspect it or recover via
+    /// [`call_force`](Self::call_force).
+    pub fn checked_call(&mut self) -> Result<&mut Output, PoisonError<&mut Self>> {
+        if self.is_poisoned() {
+            return Err(PoisonError::new(self));
         }
+        Ok(self.call())
     }
 
     /// Consumes the [`CachedFn`], calling the function if it hasn’t been called yet.
     ///
     /// # Panics
     ///
-    /// Panics if the [`CachedFn`] is in a *poisoned* state.  
-    /// This can occur if the wrapped function `f` previously panicked during execution.
-    ///
-    /// Once poisoned, the instance is considered unusable and further calls will panic.
+    /// Panics if the [`CachedFn`] is in a *poisoned* state.
     pub fn call_into(self) -> Output {
         match self.0 {
             CachedFnInner::NotCalled(f) => f(),
             CachedFnInner::Called(res) => res,
-            CachedFnInner::Poisoned => panic!("poisoned"),
+            CachedFnInner::Running => unreachable!("{RUNNING_UNREACHABLE_MSG}"),
+            CachedFnInner::Poisoned(reason) => panic!("{}", reason.panic_message()),
         }
     }
 }
@@ -204,21 +421,23 @@ where
     /// On success, the computed value is stored internally, and a mutable reference to the cached
     /// result is returned.
     ///
-    /// If the function returns an error, the [`CachedFn`] enters a **poisoned** state. Once
-    /// poisoned, the instance must be **dropped** and never reused. This prevents further calls
-    /// from observing or reusing potentially inconsistent or partially initialized state.
-    ///
-    /// Subsequent calls to a poisoned [`CachedFn`] will unconditionally panic.
+    /// If the function returns an error, the [`CachedFn`] enters a **poisoned** state: further
+    /// calls to this method or [`call`](CachedFn::call) will panic until the instance is
+    /// recovered via [`call_force`](CachedFn::call_force)/[`try_call_force`](CachedFn::try_call_force),
+    /// which attempt the computation again.
+    /// [`checked_poisoning_try_call`](Self::checked_poisoning_try_call) reports the poison as an
+    /// error instead of panicking, without recovering the instance.
     ///
     /// # Panics
     ///
-    /// Panics if the [`CachedFn`] is in a *poisoned* state.  
+    /// Panics if the [`CachedFn`] is in a *poisoned* state.
     /// This can occur if:
     /// - The wrapped function `f` previously panicked during execution, or
     /// - A prior fallible call (via [`poisoning_try_call`](#method.poisoning_try_call)) returned an
     ///   error.
     ///
-    /// Once poisoned, the instance is considered unusable and further calls will panic.
+    /// See [`poison_reason`](CachedFn::poison_reason) to tell the cause apart, or use
+    /// [`checked_poisoning_try_call`](Self::checked_poisoning_try_call) to avoid panicking.
     ///
     /// # Examples
     ///
@@ -238,31 +457,66 @@ where
         let inner = &mut self.0;
         match inner {
             CachedFnInner::NotCalled(_) => {
-                let f = inner.set_poisoned().into_not_called().unwrap();
-                inner.set_called(f()?);
-                Ok(inner.as_called_mut().unwrap())
+                let f = inner.begin_running();
+                match run_guarded(&mut *inner, f) {
+                    Ok(output) => {
+                        inner.set_called(output);
+                        Ok(inner.as_called_mut().unwrap())
+                    }
+                    Err(err) => {
+                        inner.set_poisoned(PoisonReason::Errored);
+                        Err(err)
+                    }
+                }
             }
             CachedFnInner::Called(res) => Ok(res),
-            CachedFnInner::Poisoned => panic!("poisoned"),
+            CachedFnInner::Running => unreachable!("{RUNNING_UNREACHABLE_MSG}"),
+            CachedFnInner::Poisoned(reason) => panic!("{}", reason.panic_message()),
+        }
+    }
+
+    /// Calls the function if it hasn’t been called yet and caches its result, without panicking
+    /// if the [`CachedFn`] is poisoned.
+    ///
+    /// Returns `Err(`[`PoisonError`]`)` if the instance is already poisoned. Otherwise, forwards
+    /// to [`poisoning_try_call`](Self::poisoning_try_call) and wraps its result in `Ok`, so this
+    /// returns `Ok(Err(e))` if the wrapped function fails, poisoning the instance in the process.
+    ///
+    /// The return type is `Result<Result<&mut Output, E>, PoisonError<&mut Self>>` rather than a
+    /// flattened `Result<&mut Output, PoisonError<&mut Self>>`: flattening would require either
+    /// discarding `E` or folding it into `PoisonError`, and callers need `E` to inspect *why* the
+    /// wrapped function failed. The outer `Result` distinguishes "already poisoned" from "this
+    /// call itself failed"; the inner one carries the wrapped function's own error.
+    pub fn checked_poisoning_try_call(
+        &mut self,
+    ) -> Result<Result<&mut Output, E>, PoisonError<&mut Self>> {
+        if self.is_poisoned() {
+            return Err(PoisonError::new(self));
         }
+        Ok(self.poisoning_try_call())
     }
 
     /// Consumes the [`CachedFn`], calling the function if necessary and returning its result.
     ///
     /// # Panics
     ///
-    /// Panics if the [`CachedFn`] is in a *poisoned* state.  
+    /// Panics if the [`CachedFn`] is in a *poisoned* state.
     /// This can occur if:
     /// - The wrapped function `f` previously panicked during execution, or
     /// - A prior fallible call (via [`poisoning_try_call`](#method.poisoning_try_call)) returned an
     ///   error.
     ///
-    /// Once poisoned, the instance is considered unusable and further calls will panic.
+    /// Since this consumes `self`, a poisoned instance can't be recovered through it; use
+    /// [`call_force`](CachedFn::call_force)/[`try_call_force`](CachedFn::try_call_force) on
+    /// `&mut self` instead if recovery is needed, or
+    /// [`checked_poisoning_try_call`](Self::checked_poisoning_try_call) to detect poison without
+    /// panicking.
     pub fn try_call_into(self) -> Result<Output, E> {
         match self.0 {
             CachedFnInner::NotCalled(f) => f(),
             CachedFnInner::Called(res) => Ok(res),
-            CachedFnInner::Poisoned => panic!("poisoned"),
+            CachedFnInner::Running => unreachable!("{RUNNING_UNREACHABLE_MSG}"),
+            CachedFnInner::Poisoned(reason) => panic!("{}", reason.panic_message()),
         }
     }
 
@@ -277,13 +531,17 @@ where
     ///
     /// # Panics
     ///
-    /// Panics if the [`CachedFn`] is in a *poisoned* state.  
+    /// Panics if the [`CachedFn`] is in a *poisoned* state.
     /// This can occur if:
     /// - The wrapped function `f` previously panicked during execution, or
     /// - A prior fallible call (via [`poisoning_try_call`](#method.poisoning_try_call)) returned an
     ///   error.
     ///
-    /// Once poisoned, the instance is considered unusable and further calls will panic.
+    /// Since this consumes `self`, a poisoned instance can't be recovered through it; use
+    /// [`call_force`](CachedFn::call_force)/[`try_call_force`](CachedFn::try_call_force) on
+    /// `&mut self` instead if recovery is needed, or
+    /// [`checked_poisoning_try_call`](Self::checked_poisoning_try_call) to detect poison without
+    /// panicking.
     pub fn safe_try_call(self) -> Result<Self, E> {
         match self.0 {
             CachedFnInner::NotCalled(f) => {
@@ -291,7 +549,8 @@ where
                 Ok(Self(CachedFnInner::Called(output)))
             }
             CachedFnInner::Called(_) => Ok(self),
-            CachedFnInner::Poisoned => panic!("poisoned"),
+            CachedFnInner::Running => unreachable!("{RUNNING_UNREACHABLE_MSG}"),
+            CachedFnInner::Poisoned(reason) => panic!("{}", reason.panic_message()),
         }
     }
 }
@@ -312,13 +571,15 @@ where
     ///
     /// # Panics
     ///
-    /// Panics if the [`CachedFn`] is in a *poisoned* state.  
+    /// Panics if the [`CachedFn`] is in a *poisoned* state.
     /// This can occur if:
     /// - The wrapped function `f` previously panicked during execution, or
     /// - A prior fallible call (via [`poisoning_try_call`](#method.poisoning_try_call)) returned an
     ///   error.
     ///
-    /// Once poisoned, the instance is considered unusable and further calls will panic.
+    /// See [`poison_reason`](CachedFn::poison_reason) to tell the cause apart, or use
+    /// [`call_force`](CachedFn::call_force)/[`try_call_force`](CachedFn::try_call_force) to
+    /// recover instead of panicking.
     ///
     /// # Examples
     ///
@@ -350,8 +611,8 @@ where
         let inner = &mut self.0;
         match inner {
             CachedFnInner::NotCalled(_) => {
-                let mut f = inner.set_poisoned().into_not_called().unwrap();
-                match f() {
+                let mut f = inner.begin_running();
+                match run_guarded(&mut *inner, &mut f) {
                     Ok(output) => {
                         inner.set_called(output);
                         Ok(inner.as_called_mut().unwrap())
@@ -363,7 +624,8 @@ where
                 }
             }
             CachedFnInner::Called(res) => Ok(res),
-            CachedFnInner::Poisoned => panic!("poisoned"),
+            CachedFnInner::Running => unreachable!("{RUNNING_UNREACHABLE_MSG}"),
+            CachedFnInner::Poisoned(reason) => panic!("{}", reason.panic_message()),
         }
     }
 }
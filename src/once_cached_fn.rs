@@ -0,0 +1,163 @@
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// No call has been made yet.
+const INCOMPLETE: usize = 0;
+/// A call is currently running.
+const RUNNING: usize = 1;
+/// A call has completed and the output is stored.
+const COMPLETE: usize = 2;
+/// A call panicked while running, or was never completed.
+const POISONED: usize = 3;
+
+/// A thread-safe, lazily evaluated function that caches its result after the first call.
+///
+/// Unlike [`CachedFn`](crate::CachedFn), [`OnceCachedFn::call`] only requires a shared reference
+/// `&self`, so an [`OnceCachedFn`] can be stored in a `static` and safely raced by multiple
+/// threads: the first thread to reach [`call`](Self::call) runs the wrapped function while every
+/// other caller waits for its result.
+///
+/// Construction is a `const fn`, so an [`OnceCachedFn`] can be used directly as the initializer of
+/// a `static`.
+pub struct OnceCachedFn<F, Output> {
+    state: AtomicUsize,
+    func: UnsafeCell<Option<F>>,
+    data: UnsafeCell<MaybeUninit<Output>>,
+}
+
+// SAFETY: access to `func` and `data` is synchronized through `state`: only the thread that wins
+// the `INCOMPLETE -> RUNNING` transition touches `func`, and `data` is only read once `state` is
+// observed as `COMPLETE`, which happens-after the write thanks to the `Release`/`Acquire` pair.
+unsafe impl<F: Send, Output: Send> Send for OnceCachedFn<F, Output> {}
+unsafe impl<F: Send, Output: Send + Sync> Sync for OnceCachedFn<F, Output> {}
+
+impl<F, Output> Drop for OnceCachedFn<F, Output> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == COMPLETE {
+            // SAFETY: `state` is `COMPLETE`, so `data` was initialized by `run` and is never
+            // written to again; `&mut self` guarantees no other reference to `data` is alive.
+            unsafe { (*self.data.get()).assume_init_drop() };
+        }
+    }
+}
+
+impl<F, Output> OnceCachedFn<F, Output> {
+    /// Creates a new [`OnceCachedFn`] wrapping the given function.
+    #[must_use]
+    #[inline]
+    pub const fn new(func: F) -> Self {
+        Self {
+            state: AtomicUsize::new(INCOMPLETE),
+            func: UnsafeCell::new(Some(func)),
+            data: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Returns the cached output if the function has already been called, without blocking.
+    #[must_use]
+    #[inline]
+    pub fn get(&self) -> Option<&Output> {
+        if self.state.load(Ordering::Acquire) == COMPLETE {
+            // SAFETY: `state` is `COMPLETE`, so `data` has been initialized and is never written
+            // to again.
+            Some(unsafe { &*(*self.data.get()).as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if a previous call to the wrapped function panicked.
+    #[must_use]
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        self.state.load(Ordering::Acquire) == POISONED
+    }
+}
+
+impl<F, Output> OnceCachedFn<F, Output>
+where
+    F: FnOnce() -> Output,
+{
+    /// Calls the function if it hasn't been called yet and caches its result.
+    ///
+    /// If another thread is already running the call, this spins (or, with the `std` feature,
+    /// yields the current thread) until that call completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`OnceCachedFn`] is poisoned, which happens if the wrapped function
+    /// previously panicked during execution.
+    pub fn call(&self) -> &Output {
+        // Fast path: the value is already there, no need to touch the state machine.
+        if self.state.load(Ordering::Acquire) == COMPLETE {
+            // SAFETY: see `get`.
+            return unsafe { &*(*self.data.get()).as_ptr() };
+        }
+
+        match self
+            .state
+            .compare_exchange(INCOMPLETE, RUNNING, Ordering::Acquire, Ordering::Acquire)
+        {
+            Ok(_) => self.run(),
+            Err(_) => self.wait_until_complete(),
+        }
+    }
+
+    /// Runs the wrapped function after this thread has won the `INCOMPLETE -> RUNNING` race, and
+    /// stores its result.
+    fn run(&self) -> &Output {
+        struct PoisonOnUnwind<'a> {
+            state: &'a AtomicUsize,
+            armed: bool,
+        }
+
+        impl Drop for PoisonOnUnwind<'_> {
+            fn drop(&mut self) {
+                if self.armed {
+                    self.state.store(POISONED, Ordering::Release);
+                }
+            }
+        }
+
+        let mut guard = PoisonOnUnwind { state: &self.state, armed: true };
+
+        // SAFETY: only the thread that won the CAS reaches this point, and it is the only thread
+        // allowed to touch `func` while `state` is `RUNNING`.
+        let f = unsafe { (*self.func.get()).take().unwrap() };
+        let output = f();
+        guard.armed = false;
+
+        // SAFETY: same reasoning as above, for `data`.
+        unsafe {
+            (*self.data.get()).write(output);
+        }
+        self.state.store(COMPLETE, Ordering::Release);
+
+        // SAFETY: `data` was just initialized above.
+        unsafe { &*(*self.data.get()).as_ptr() }
+    }
+
+    /// Spins (or, with the `std` feature, yields) until `state` is no longer `RUNNING`.
+    fn wait_until_complete(&self) -> &Output {
+        loop {
+            match self.state.load(Ordering::Acquire) {
+                COMPLETE => return unsafe { &*(*self.data.get()).as_ptr() },
+                POISONED => panic!("OnceCachedFn is poisoned: the wrapped function previously panicked"),
+                _ => Self::wait_hint(),
+            }
+        }
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline]
+    fn wait_hint() {
+        core::hint::spin_loop();
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn wait_hint() {
+        std::thread::yield_now();
+    }
+}
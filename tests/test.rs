@@ -1,4 +1,6 @@
-use cached_fn::CachedFn;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use cached_fn::{CachedFn, CachedValue, OnceCachedFn, PoisonReason};
 
 #[test]
 fn test_cached_fn() {
@@ -16,3 +18,100 @@ fn test_cached_fn() {
 
     assert_eq!(x, 1);
 }
+
+static ONCE_CACHED: OnceCachedFn<fn() -> usize, usize> = OnceCachedFn::new(compute);
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn compute() -> usize {
+    CALLS.fetch_add(1, Ordering::Relaxed);
+    42
+}
+
+#[test]
+fn test_once_cached_fn() {
+    assert!(ONCE_CACHED.get().is_none());
+
+    assert_eq!(*ONCE_CACHED.call(), 42);
+    assert_eq!(*ONCE_CACHED.call(), 42);
+    assert_eq!(*ONCE_CACHED.call(), 42);
+
+    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    assert_eq!(ONCE_CACHED.get(), Some(&42));
+}
+
+#[test]
+fn test_once_cached_fn_drops_value() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct DropCounter;
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let once = OnceCachedFn::new(|| DropCounter);
+    once.call();
+    drop(once);
+
+    assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_call_force_recovers_from_poison() {
+    let mut c = CachedFn::new(|| -> u32 { panic!("boom") });
+
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        c.call();
+    }));
+    assert!(c.is_poisoned());
+
+    let output = c.call_force(|state| {
+        assert!(state.is_poisoned());
+        7
+    });
+    assert_eq!(*output, 7);
+    assert!(c.is_called());
+}
+
+#[test]
+fn test_checked_call_reports_poison_without_panicking() {
+    let mut c = CachedFn::new(|| -> u32 { panic!("boom") });
+
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        c.call();
+    }));
+    assert!(c.is_poisoned());
+
+    let err = c.checked_call().unwrap_err();
+    assert!(err.into_inner().is_poisoned());
+}
+
+#[test]
+fn test_poison_reason_distinguishes_panic_from_error() {
+    let mut panicked = CachedFn::new(|| -> u32 { panic!("boom") });
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        panicked.call();
+    }));
+    assert_eq!(panicked.poison_reason(), Some(PoisonReason::Panicked));
+
+    let mut errored = CachedFn::new(|| -> Result<u32, &'static str> { Err("nope") });
+    let _ = errored.poisoning_try_call();
+    assert_eq!(errored.poison_reason(), Some(PoisonReason::Errored));
+}
+
+#[test]
+fn test_cached_value() {
+    let mut x = 0usize;
+    let mut cached = CachedValue::<usize>::new();
+
+    assert!(cached.get().is_none());
+
+    assert_eq!(*cached.get_or_init(|| { x += 1; x + 1 }), 2);
+    assert_eq!(*cached.get_or_init(|| { x += 1; x + 1 }), 2);
+
+    assert_eq!(x, 1);
+    assert_eq!(cached.get(), Some(&2));
+}